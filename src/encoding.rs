@@ -0,0 +1,65 @@
+//!
+//! A compact fixed-width binary encoding for closing-price rows.
+//!
+//! Each [`Record`] serializes to exactly [`RECORD_SIZE`] little-endian bytes
+//! with a stable field layout, so downstream tools can mmap/seek into a file of
+//! records without parsing text.
+//!
+
+/// Size of a single encoded record, in bytes.
+pub const RECORD_SIZE: usize = 32;
+
+/// Byte offset of the `u64` timestamp (unix nanos).
+pub const TIME_OFFSET: usize = 8;
+
+/// Byte offset of the `f64` adjusted close.
+pub const PRICE_OFFSET: usize = 16;
+
+/// Byte offset of the `f64` SMA value.
+pub const SMA_OFFSET: usize = 24;
+
+///
+/// One fixed-width quote record.
+///
+/// Layout (little-endian): a 1-byte symbol-table index, a 7-byte reserved/flags
+/// field, a `u64` timestamp, an `f64` adjusted close, and an `f64` SMA value.
+///
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Record {
+    pub symbol: u8,
+    pub flags: [u8; 7],
+    pub timestamp: u64,
+    pub adjclose: f64,
+    pub sma: f64,
+}
+
+impl Record {
+    ///
+    /// Serialize the record into `buf`, which must be at least
+    /// [`RECORD_SIZE`] bytes long.
+    ///
+    pub fn encode(&self, buf: &mut [u8]) {
+        buf[0] = self.symbol;
+        buf[1..TIME_OFFSET].copy_from_slice(&self.flags);
+        buf[TIME_OFFSET..PRICE_OFFSET].copy_from_slice(&self.timestamp.to_le_bytes());
+        buf[PRICE_OFFSET..SMA_OFFSET].copy_from_slice(&self.adjclose.to_le_bytes());
+        buf[SMA_OFFSET..RECORD_SIZE].copy_from_slice(&self.sma.to_le_bytes());
+    }
+
+    ///
+    /// Deserialize a record from `buf`, which must be at least
+    /// [`RECORD_SIZE`] bytes long.
+    ///
+    pub fn decode(buf: &[u8]) -> Self {
+        let mut flags = [0u8; 7];
+        flags.copy_from_slice(&buf[1..TIME_OFFSET]);
+        Record {
+            symbol: buf[0],
+            flags,
+            timestamp: u64::from_le_bytes(buf[TIME_OFFSET..PRICE_OFFSET].try_into().unwrap()),
+            adjclose: f64::from_le_bytes(buf[PRICE_OFFSET..SMA_OFFSET].try_into().unwrap()),
+            sma: f64::from_le_bytes(buf[SMA_OFFSET..RECORD_SIZE].try_into().unwrap()),
+        }
+    }
+}
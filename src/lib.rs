@@ -2,6 +2,8 @@ use chrono::prelude::*;
 use std::io::{Error, ErrorKind};
 use yahoo_finance_api as yahoo;
 
+pub mod encoding;
+
 ///
 /// A trait to provide a common interface for all signal calculations.
 ///
@@ -19,6 +21,31 @@ pub trait AsyncStockSignal {
     /// The signal (using the provided type) or `None` on error/invalid data.
     ///
     fn calculate(&self, series: &[f64]) -> Option<Self::SignalType>;
+
+    ///
+    /// Calculate the signal on a full OHLCV series.
+    ///
+    /// Signals that only need closing prices can rely on the default, which
+    /// forwards the closes to [`AsyncStockSignal::calculate`]. Volume- or
+    /// time-aware signals override this to use the other [`Quote`] fields.
+    ///
+    fn calculate_ohlcv(&self, series: &[Quote]) -> Option<Self::SignalType> {
+        let closes: Vec<f64> = series.iter().map(|q| q.close).collect();
+        self.calculate(&closes)
+    }
+}
+
+///
+/// A single OHLCV bar.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quote {
+    pub timestamp: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
 }
 
 pub struct PriceDifference;
@@ -63,6 +90,58 @@ impl AsyncStockSignal for WindowedSMA {
     }
 }
 
+pub struct ExponentialMA {
+    pub window: usize,
+}
+
+impl AsyncStockSignal for ExponentialMA {
+    type SignalType = Vec<f64>;
+
+    fn calculate(&self, series: &[f64]) -> Option<Self::SignalType> {
+        exp_ma(self.window, series)
+    }
+}
+
+pub struct RelativeStrengthIndex {
+    pub window: usize,
+}
+
+impl AsyncStockSignal for RelativeStrengthIndex {
+    type SignalType = Vec<f64>;
+
+    fn calculate(&self, series: &[f64]) -> Option<Self::SignalType> {
+        rsi(self.window, series)
+    }
+}
+
+pub struct WeightedAverageWindow {
+    pub window: usize,
+}
+
+impl AsyncStockSignal for WeightedAverageWindow {
+    type SignalType = Vec<f64>;
+
+    // a volume-weighted signal has nothing to weight with on a bare close
+    // series, so the price-only path is unavailable
+    fn calculate(&self, _series: &[f64]) -> Option<Self::SignalType> {
+        None
+    }
+
+    fn calculate_ohlcv(&self, series: &[Quote]) -> Option<Self::SignalType> {
+        weighted_average_window(self.window, series)
+    }
+}
+
+pub struct Volatility;
+
+impl AsyncStockSignal for Volatility {
+    type SignalType = f64;
+
+    fn calculate(&self, series: &[f64]) -> Option<Self::SignalType> {
+        volatility(series)
+    }
+}
+
 // finds the minimum value of a given series
 pub fn min(series: &[f64]) -> Option<f64> {
     if !series.is_empty() {
@@ -109,6 +188,103 @@ pub fn n_window_sma(n: usize, series: &[f64]) -> Option<Vec<f64>> {
     }
 }
 
+// calculates an exponential moving average with the given window. the series is
+// seeded with the simple average of the first `window` values, after which each
+// position is `price*k + prev*(1-k)` with `k = 2/(window+1)`.
+pub fn exp_ma(window: usize, series: &[f64]) -> Option<Vec<f64>> {
+    if series.is_empty() || window == 0 || series.len() < window {
+        None
+    } else {
+        let k = 2.0 / (window as f64 + 1.0);
+        let mut prev = series[..window].iter().sum::<f64>() / window as f64;
+        let mut ema = Vec::with_capacity(series.len() - window + 1);
+        ema.push(prev);
+        for price in &series[window..] {
+            prev = price * k + prev * (1.0 - k);
+            ema.push(prev);
+        }
+        Some(ema)
+    }
+}
+
+// turns a Wilder-smoothed average gain/loss pair into the RSI value, treating a
+// zero average loss as a fully overbought RSI of 100
+fn wilder_rsi(avg_gain: f64, avg_loss: f64) -> f64 {
+    if avg_loss == 0.0 {
+        100.0
+    } else {
+        let rs = avg_gain / avg_loss;
+        100.0 - 100.0 / (1.0 + rs)
+    }
+}
+
+// calculates the Relative Strength Index over the series. period-to-period
+// deltas are split into gains and losses, Wilder-smoothed over `window` (the
+// first average is a simple mean, then `avg = (prev*(window-1)+current)/window`).
+pub fn rsi(window: usize, series: &[f64]) -> Option<Vec<f64>> {
+    if window == 0 || series.len() <= window {
+        None
+    } else {
+        let (gains, losses): (Vec<f64>, Vec<f64>) = series
+            .windows(2)
+            .map(|w| {
+                let delta = w[1] - w[0];
+                (delta.max(0.0), (-delta).max(0.0))
+            })
+            .unzip();
+
+        let w = window as f64;
+        let mut avg_gain = gains[..window].iter().sum::<f64>() / w;
+        let mut avg_loss = losses[..window].iter().sum::<f64>() / w;
+
+        let mut out = Vec::with_capacity(gains.len() - window + 1);
+        out.push(wilder_rsi(avg_gain, avg_loss));
+        for i in window..gains.len() {
+            avg_gain = (avg_gain * (w - 1.0) + gains[i]) / w;
+            avg_loss = (avg_loss * (w - 1.0) + losses[i]) / w;
+            out.push(wilder_rsi(avg_gain, avg_loss));
+        }
+        Some(out)
+    }
+}
+
+// calculates the annualized volatility as the standard deviation of the log
+// returns `ln(p[i]/p[i-1])`, scaled by sqrt(252) trading days
+pub fn volatility(series: &[f64]) -> Option<f64> {
+    if series.len() < 2 {
+        None
+    } else {
+        let returns: Vec<f64> = series.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+        let n = returns.len() as f64;
+        let mean = returns.iter().sum::<f64>() / n;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n;
+        Some(variance.sqrt() * 252.0_f64.sqrt())
+    }
+}
+
+// calculates a streaming VWAP-style weighted average over a sliding window:
+// `sum(price*volume)/sum(volume)` for each window position, falling back to the
+// unweighted mean when a window's total volume is zero
+pub fn weighted_average_window(window: usize, quotes: &[Quote]) -> Option<Vec<f64>> {
+    if quotes.is_empty() || window == 0 || quotes.len() < window {
+        None
+    } else {
+        Some(
+            quotes
+                .windows(window)
+                .map(|w| {
+                    let total_volume: f64 = w.iter().map(|q| q.volume).sum();
+                    if total_volume == 0.0 {
+                        w.iter().map(|q| q.close).sum::<f64>() / w.len() as f64
+                    } else {
+                        w.iter().map(|q| q.close * q.volume).sum::<f64>() / total_volume
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
 pub async fn fetch_closing_data(
     ticker: &str,
     start: &DateTime<Utc>,
@@ -129,3 +305,34 @@ pub async fn fetch_closing_data(
         Ok(vec![])
     }
 }
+
+pub async fn fetch_ohlcv_data(
+    ticker: &str,
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+) -> std::io::Result<Vec<Quote>> {
+    let provider = yahoo::YahooConnector::new();
+    let response = provider
+        .get_quote_history(ticker, *start, *end)
+        .await
+        .map_err(|_| Error::from(ErrorKind::InvalidData))?;
+    let mut quotes = response
+        .quotes()
+        .map_err(|_| Error::from(ErrorKind::InvalidData))?;
+    if !quotes.is_empty() {
+        quotes.sort_by_cached_key(|q| q.timestamp);
+        Ok(quotes
+            .iter()
+            .map(|q| Quote {
+                timestamp: q.timestamp,
+                open: q.open as f64,
+                high: q.high as f64,
+                low: q.low as f64,
+                close: q.adjclose as f64,
+                volume: q.volume as f64,
+            })
+            .collect())
+    } else {
+        Ok(vec![])
+    }
+}
@@ -1,6 +1,11 @@
 use chrono::{prelude::*, ParseError};
+use stocks::encoding::{Record, RECORD_SIZE};
 use stocks::*;
 
+use futures::stream::{self, StreamExt};
+use polars::prelude::*;
+use std::fs::File;
+use std::io::Write;
 use structopt::StructOpt;
 
 fn parse_date(src: &str) -> Result<DateTime<Utc>, ParseError> {
@@ -14,9 +19,252 @@ struct Opts {
     #[structopt(short = "s", long, default_value = "AAPL,MSFT,UBER,GOOG")]
     symbols: String,
 
+    /// File with comma/newline-delimited ticker symbols (overrides --symbols)
+    #[structopt(long)]
+    symbols_file: Option<String>,
+
+    /// Maximum number of symbols to fetch concurrently
+    #[structopt(long, default_value = "16")]
+    max_concurrent: usize,
+
     /// Start date of the data
     #[structopt(parse(try_from_str=parse_date), short = "f", long)]
     from: DateTime<Utc>,
+
+    /// Keep running and re-fetch each symbol on every --interval tick
+    #[structopt(long)]
+    stream: bool,
+
+    /// Seconds between re-fetches in --stream mode
+    #[structopt(long, default_value = "30")]
+    interval: u64,
+
+    /// Output format: csv, bin, parquet, or json
+    #[structopt(long, default_value = "csv")]
+    format: String,
+
+    /// Write the signal table to this file (uses --format parquet|csv|json)
+    #[structopt(long)]
+    output: Option<String>,
+
+    /// Read a binary record file back and reprint it as CSV, then exit
+    #[structopt(long)]
+    decode: Option<String>,
+}
+
+// reads the list of symbols from the given file, splitting on commas and
+// whitespace so both comma- and newline-delimited ticker files work
+fn read_symbols_file(path: &str) -> std::io::Result<Vec<String>> {
+    let contents = std::fs::read_to_string(path)?;
+    Ok(contents
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+// renders a single CSV row for a symbol from its closing prices, or None when
+// there is no data for the period
+fn format_row(from: &DateTime<Utc>, symbol: &str, closes: &[f64]) -> Option<String> {
+    if closes.is_empty() {
+        return None;
+    }
+    // min/max of the period. unwrap() because those are Option types
+    let period_max: f64 = max(closes).unwrap();
+    let period_min: f64 = min(closes).unwrap();
+    let last_price = *closes.last().unwrap_or(&0.0);
+    let (_, pct_change) = price_diff(closes).unwrap_or((0.0, 0.0));
+    let sma = n_window_sma(30, closes).unwrap_or_default();
+
+    // a simple way to output CSV data
+    Some(format!(
+        "{},{},${:.2},{:.2}%,${:.2},${:.2},${:.2}",
+        from.to_rfc3339(),
+        symbol,
+        last_price,
+        pct_change * 100.0,
+        period_min,
+        period_max,
+        sma.last().unwrap_or(&0.0)
+    ))
+}
+
+// runs the continuous streaming pipeline: one fetcher task per symbol pushes
+// `(symbol, closes)` batches into an mpsc channel on every interval tick, and a
+// single consumer computes the signals and writes one CSV row per batch. the
+// function loops forever, so callers treat it as the long-running service path.
+async fn run_stream(symbols: Vec<String>, from: DateTime<Utc>, interval: u64) {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<(String, Vec<f64>)>(symbols.len().max(1));
+
+    for symbol in symbols {
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval));
+            loop {
+                ticker.tick().await;
+                let to = Utc::now();
+                match fetch_closing_data(&symbol, &from, &to).await {
+                    Ok(closes) if !closes.is_empty() => {
+                        if tx.send((symbol.clone(), closes)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => eprintln!("error fetching {}: {}", symbol, e),
+                }
+            }
+        });
+    }
+    // drop our own sender so the channel closes once every fetcher is gone
+    drop(tx);
+
+    while let Some((symbol, closes)) = rx.recv().await {
+        if let Some(row) = format_row(&from, &symbol, &closes) {
+            println!("{}", row);
+        }
+    }
+}
+
+// fetches each symbol's OHLCV series concurrently and writes one fixed-width
+// record per data point to stdout, so the whole quote series (not just a
+// per-symbol summary) can be mmap'd/seeked downstream. each record carries the
+// quote's own timestamp and a per-row SMA, and a 1-byte symbol-table index into
+// the table printed to stderr. the index is a single byte, so at most 256
+// symbols can be encoded; anything larger is rejected rather than silently
+// wrapped past 255.
+async fn run_bin(symbols: Vec<String>, from: DateTime<Utc>, to: DateTime<Utc>) {
+    if symbols.len() > u8::MAX as usize + 1 {
+        eprintln!(
+            "error: binary format supports at most {} symbols (got {})",
+            u8::MAX as usize + 1,
+            symbols.len()
+        );
+        return;
+    }
+
+    let mut results: Vec<(String, Vec<Quote>)> = stream::iter(symbols.iter())
+        .map(|symbol| async move {
+            match fetch_ohlcv_data(symbol, &from, &to).await {
+                Ok(quotes) if !quotes.is_empty() => Some((symbol.clone(), quotes)),
+                Ok(_) => None,
+                Err(e) => {
+                    eprintln!("error fetching {}: {}", symbol, e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(16)
+        .filter_map(|row| async move { row })
+        .collect()
+        .await;
+
+    // stable order so the symbol-table indices are deterministic across runs
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    let mut buf = [0u8; RECORD_SIZE];
+    for (index, (symbol, quotes)) in results.iter().enumerate() {
+        // emit the symbol-table mapping so the 1-byte indices in the records
+        // can be resolved back to tickers
+        eprintln!("{}\t{}", index, symbol);
+        let closes: Vec<f64> = quotes.iter().map(|q| q.close).collect();
+        let sma = n_window_sma(30, &closes).unwrap_or_default();
+        for (i, quote) in quotes.iter().enumerate() {
+            // n_window_sma yields one value per full window, starting at the
+            // (window-1)th row; earlier rows have no SMA yet
+            let sma = i.checked_sub(29).and_then(|j| sma.get(j)).copied();
+            let record = Record {
+                symbol: index as u8,
+                flags: [0u8; 7],
+                timestamp: quote.timestamp,
+                adjclose: quote.close,
+                sma: sma.unwrap_or(0.0),
+            };
+            record.encode(&mut buf);
+            out.write_all(&buf).expect("could not write record");
+        }
+    }
+}
+
+// reads a file of fixed-width binary records back and reprints it as CSV, so
+// the encoding round-trips end to end
+fn decode_file(path: &str) -> std::io::Result<()> {
+    let bytes = std::fs::read(path)?;
+    println!("timestamp,symbol,price,sma");
+    for chunk in bytes.chunks_exact(RECORD_SIZE) {
+        let record = Record::decode(chunk);
+        println!(
+            "{},{},${:.2},${:.2}",
+            record.timestamp, record.symbol, record.adjclose, record.sma
+        );
+    }
+    Ok(())
+}
+
+// collects the per-symbol rows into a typed Polars `DataFrame` and writes it to
+// `path` in the requested columnar format, so downstream quant tooling can load
+// the signal table directly instead of re-parsing CSV text
+fn write_dataframe(
+    results: &[(String, Vec<f64>)],
+    from: &DateTime<Utc>,
+    format: &str,
+    path: &str,
+) -> PolarsResult<()> {
+    let symbol: Vec<String> = results.iter().map(|(s, _)| s.clone()).collect();
+    let mut last_price = Vec::with_capacity(results.len());
+    let mut pct_change = Vec::with_capacity(results.len());
+    let mut min_price = Vec::with_capacity(results.len());
+    let mut max_price = Vec::with_capacity(results.len());
+    let mut sma = Vec::with_capacity(results.len());
+    let mut ema = Vec::with_capacity(results.len());
+    let mut rsi_col = Vec::with_capacity(results.len());
+    let mut volatility_col = Vec::with_capacity(results.len());
+
+    let last = |v: Vec<f64>| *v.last().unwrap_or(&0.0);
+    for (_, closes) in results {
+        last_price.push(*closes.last().unwrap_or(&0.0));
+        pct_change.push(price_diff(closes).unwrap_or((0.0, 0.0)).1 * 100.0);
+        min_price.push(min(closes).unwrap_or(0.0));
+        max_price.push(max(closes).unwrap_or(0.0));
+        sma.push(last(n_window_sma(30, closes).unwrap_or_default()));
+        ema.push(last(exp_ma(30, closes).unwrap_or_default()));
+        rsi_col.push(last(rsi(14, closes).unwrap_or_default()));
+        volatility_col.push(volatility(closes).unwrap_or(0.0));
+    }
+
+    let period_start = vec![from.timestamp_millis(); results.len()];
+    let mut df = df![
+        "period start" => period_start,
+        "symbol" => symbol,
+        "last price" => last_price,
+        "pct change" => pct_change,
+        "min" => min_price,
+        "max" => max_price,
+        "sma" => sma,
+        "ema" => ema,
+        "rsi" => rsi_col,
+        "volatility" => volatility_col,
+    ]?;
+    df.try_apply("period start", |s| {
+        s.cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+    })?;
+
+    let mut file = File::create(path)?;
+    match format {
+        "parquet" => {
+            ParquetWriter::new(&mut file).finish(&mut df)?;
+        }
+        "json" => {
+            JsonWriter::new(&mut file)
+                .with_json_format(JsonFormat::Json)
+                .finish(&mut df)?;
+        }
+        _ => {
+            CsvWriter::new(&mut file).finish(&mut df)?;
+        }
+    }
+    Ok(())
 }
 
 #[tokio::main]
@@ -24,31 +272,66 @@ async fn main() {
     let opts = Opts::from_args();
     let to = Utc::now();
 
+    if let Some(path) = &opts.decode {
+        decode_file(path).expect("could not decode file");
+        return;
+    }
+
+    let symbols: Vec<String> = match &opts.symbols_file {
+        Some(path) => read_symbols_file(path).expect("could not read symbols file"),
+        None => opts.symbols.split(',').map(str::to_string).collect(),
+    };
+
+    if opts.stream {
+        // a simple way to output a CSV header
+        println!("period start,symbol,price,change %,min,max,30d avg");
+        run_stream(symbols, opts.from, opts.interval).await;
+        return;
+    }
+
+    // the binary format needs the full per-row quote series (timestamps and
+    // all), so it fetches OHLCV rather than the bare closes the other paths use
+    if opts.output.is_none() && opts.format == "bin" {
+        run_bin(symbols, opts.from, to).await;
+        return;
+    }
+
+    // fetch all symbols concurrently, bounded by --max-concurrent, and collect
+    // the closing series as they complete. a symbol that errors is logged to
+    // stderr and skipped so one bad ticker does not abort a whole-index scan.
+    let mut results: Vec<(String, Vec<f64>)> = stream::iter(symbols.iter())
+        .map(|symbol| {
+            let from = opts.from;
+            async move {
+                match fetch_closing_data(symbol, &from, &to).await {
+                    Ok(closes) if !closes.is_empty() => Some((symbol.clone(), closes)),
+                    Ok(_) => None,
+                    Err(e) => {
+                        eprintln!("error fetching {}: {}", symbol, e);
+                        None
+                    }
+                }
+            }
+        })
+        .buffer_unordered(opts.max_concurrent)
+        .filter_map(|row| async move { row })
+        .collect()
+        .await;
+
+    // output stays deterministic regardless of completion order
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if let Some(path) = &opts.output {
+        write_dataframe(&results, &opts.from, &opts.format, path)
+            .expect("could not write output file");
+        return;
+    }
+
     // a simple way to output a CSV header
     println!("period start,symbol,price,change %,min,max,30d avg");
-    for symbol in opts.symbols.split(',') {
-        let closes = fetch_closing_data(&symbol, &opts.from, &to)
-            .await
-            .expect("msg");
-        if !closes.is_empty() {
-            // min/max of the period. unwrap() because those are Option types
-            let period_max: f64 = max(&closes).unwrap();
-            let period_min: f64 = min(&closes).unwrap();
-            let last_price = *closes.last().unwrap_or(&0.0);
-            let (_, pct_change) = price_diff(&closes).unwrap_or((0.0, 0.0));
-            let sma = n_window_sma(30, &closes).unwrap_or_default();
-
-            // a simple way to output CSV data
-            println!(
-                "{},{},${:.2},{:.2}%,${:.2},${:.2},${:.2}",
-                opts.from.to_rfc3339(),
-                symbol,
-                last_price,
-                pct_change * 100.0,
-                period_min,
-                period_max,
-                sma.last().unwrap_or(&0.0)
-            );
+    for (symbol, closes) in &results {
+        if let Some(row) = format_row(&opts.from, symbol, closes) {
+            println!("{}", row);
         }
     }
 }
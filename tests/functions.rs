@@ -1,6 +1,21 @@
 #![allow(non_snake_case)]
+use stocks::encoding::{Record, RECORD_SIZE};
 use stocks::*;
 
+#[test]
+fn test_Record_roundtrip() {
+    let record = Record {
+        symbol: 7,
+        flags: [1, 2, 3, 4, 5, 6, 7],
+        timestamp: 1_609_459_200_000_000_000,
+        adjclose: 132.69,
+        sma: 130.25,
+    };
+    let mut buf = [0u8; RECORD_SIZE];
+    record.encode(&mut buf);
+    assert_eq!(Record::decode(&buf), record);
+}
+
 #[test]
 fn test_PriceDifference_calculate() {
     let signal = PriceDifference {};
@@ -49,6 +64,100 @@ fn test_MaxPrice_calculate() {
     );
 }
 
+// iterative indicators accumulate floating-point error, so value cases are
+// compared with a small tolerance rather than bit-for-bit equality
+fn approx_eq(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+#[test]
+fn test_ExponentialMA_calculate() {
+    let series = vec![2.0, 4.5, 5.3, 6.5, 4.7];
+
+    let signal = ExponentialMA { window: 3 };
+    let ema = signal.calculate(&series).unwrap();
+    let expected = [3.9333333333333336, 5.216666666666667, 4.958333333333333];
+    assert_eq!(ema.len(), expected.len());
+    assert!(ema.iter().zip(expected).all(|(a, b)| approx_eq(*a, b)));
+
+    let signal = ExponentialMA { window: 10 };
+    assert_eq!(signal.calculate(&series), None);
+
+    let signal = ExponentialMA { window: 3 };
+    assert_eq!(signal.calculate(&[]), None);
+}
+
+#[test]
+fn test_RelativeStrengthIndex_calculate() {
+    let signal = RelativeStrengthIndex { window: 3 };
+
+    // a strictly rising series has no losses, so RSI pins at 100
+    assert_eq!(
+        signal.calculate(&[1.0, 2.0, 3.0, 4.0, 5.0]),
+        Some(vec![100.0, 100.0])
+    );
+
+    // too short to form `window` deltas
+    assert_eq!(signal.calculate(&[1.0, 2.0, 3.0]), None);
+    assert_eq!(signal.calculate(&[]), None);
+
+    let signal = RelativeStrengthIndex { window: 2 };
+    let rsi = signal.calculate(&[10.0, 11.0, 10.0, 12.0]).unwrap();
+    assert_eq!(rsi.len(), 2);
+    assert!(rsi.iter().all(|v| (0.0..=100.0).contains(v)));
+}
+
+#[test]
+fn test_Volatility_calculate() {
+    let signal = Volatility {};
+    assert_eq!(signal.calculate(&[]), None);
+    assert_eq!(signal.calculate(&[100.0]), None);
+
+    // a flat series has zero log returns, hence zero volatility
+    assert!(approx_eq(signal.calculate(&[50.0, 50.0, 50.0]).unwrap(), 0.0));
+
+    let vol = signal.calculate(&[100.0, 110.0, 105.0, 115.0]).unwrap();
+    assert!(vol > 0.0 && vol.is_finite());
+}
+
+#[test]
+fn test_WeightedAverageWindow_calculate() {
+    fn bar(close: f64, volume: f64) -> Quote {
+        Quote {
+            timestamp: 0,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume,
+        }
+    }
+
+    let quotes = vec![
+        bar(10.0, 100.0),
+        bar(11.0, 300.0),
+        bar(12.0, 0.0),
+        bar(13.0, 0.0),
+    ];
+
+    let signal = WeightedAverageWindow { window: 2 };
+    let vwap = signal.calculate_ohlcv(&quotes).unwrap();
+    assert_eq!(vwap.len(), 3);
+    // first window: (10*100 + 11*300) / 400 = 10.75
+    assert!(approx_eq(vwap[0], 10.75));
+    // second window: (11*300 + 12*0) / 300 = 11.0
+    assert!(approx_eq(vwap[1], 11.0));
+    // third window has zero total volume, so it falls back to the mean
+    assert!(approx_eq(vwap[2], 12.5));
+
+    // insufficient length / empty yield None
+    assert_eq!(signal.calculate_ohlcv(&[bar(10.0, 1.0)]), None);
+    assert_eq!(signal.calculate_ohlcv(&[]), None);
+
+    // the price-only path is unavailable for a volume-weighted signal
+    assert_eq!(signal.calculate(&[10.0, 11.0]), None);
+}
+
 #[test]
 fn test_WindowedSMA_calculate() {
     let series = vec![2.0, 4.5, 5.3, 6.5, 4.7];